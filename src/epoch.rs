@@ -0,0 +1,107 @@
+//! Epoch Time validation, as described in RFC 6887 §8.5.
+//!
+//! Every PCP response and `ANNOUNCE` carries an Epoch Time. When the epoch
+//! reported by a server jumps in a way that isn't explained by the passage
+//! of local time, the gateway has lost its mapping state and every mapping
+//! held against that server needs to be re-requested. [`EpochTracker`] keeps
+//! the bookkeeping needed to make that call per server.
+//!
+//! **This module is detection-only**: [`EpochTracker`] only decides whether
+//! a given Epoch Time means the server has reset. The actual recreation —
+//! walking live mappings back to `State::Requested` and re-sending their
+//! original `Event::InboundMap`/`OutboundMap` — lives next to the table of
+//! live mappings in `crate::handle` (`LiveMappings`), not here:
+//! [`crate::handle::report_epoch`] calls [`EpochTracker::observe`] and, on a
+//! detected reset, both surfaces `Error::GatewayReset` and drives that
+//! recreation.
+
+use std::time::Instant;
+
+/// Per-server epoch bookkeeping used to detect a PCP server (gateway)
+/// restart.
+#[derive(Debug, Clone, Copy)]
+pub struct EpochTracker {
+    prev_client_time: Instant,
+    prev_server_epoch: u32,
+    /// `true` until the first packet has been observed, since the first
+    /// packet from a server is always accepted as a baseline.
+    first: bool,
+}
+
+impl EpochTracker {
+    /// Creates a tracker with no prior observation; the next call to
+    /// [`EpochTracker::observe`] is always accepted.
+    pub fn new() -> Self {
+        EpochTracker {
+            prev_client_time: Instant::now(),
+            prev_server_epoch: 0,
+            first: true,
+        }
+    }
+
+    /// Feeds a newly received Epoch Time into the tracker.
+    ///
+    /// Returns `true` if this epoch indicates the server has reset (lost
+    /// its mapping state) since the last observation. `now` is the local
+    /// monotonic time the packet carrying `epoch` was received at.
+    pub fn observe(&mut self, now: Instant, epoch: u32) -> bool {
+        if self.first {
+            self.first = false;
+            self.prev_client_time = now;
+            self.prev_server_epoch = epoch;
+            return false;
+        }
+
+        let client_delta = now.saturating_duration_since(self.prev_client_time).as_secs() as i64;
+        let server_delta = i64::from(epoch) - i64::from(self.prev_server_epoch);
+
+        let reset = server_delta + 2 < client_delta - client_delta / 16
+            || client_delta + 2 < server_delta - server_delta / 16;
+
+        self.prev_client_time = now;
+        self.prev_server_epoch = epoch;
+        reset
+    }
+}
+
+impl Default for EpochTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn first_observation_is_always_accepted() {
+        let mut tracker = EpochTracker::new();
+        assert!(!tracker.observe(Instant::now(), 123_456));
+    }
+
+    #[test]
+    fn epoch_advancing_with_local_time_is_not_a_reset() {
+        let mut tracker = EpochTracker::new();
+        let t0 = Instant::now();
+        tracker.observe(t0, 1000);
+        assert!(!tracker.observe(t0 + Duration::from_secs(10), 1010));
+    }
+
+    #[test]
+    fn epoch_going_backward_is_a_reset() {
+        let mut tracker = EpochTracker::new();
+        let t0 = Instant::now();
+        tracker.observe(t0, 1000);
+        assert!(tracker.observe(t0 + Duration::from_secs(10), 5));
+    }
+
+    #[test]
+    fn epoch_frozen_while_local_time_moves_on_is_a_reset() {
+        let mut tracker = EpochTracker::new();
+        let t0 = Instant::now();
+        tracker.observe(t0, 1000);
+        assert!(tracker.observe(t0 + Duration::from_secs(600), 1000));
+    }
+}
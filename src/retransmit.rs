@@ -0,0 +1,306 @@
+//! UDP retransmission timing and pending-request bookkeeping, as described
+//! in RFC 6887 §8.1.1.
+//!
+//! PCP requests ride on UDP, which may silently drop the datagram sent in
+//! response to an `Event::InboundMap`/`Event::OutboundMap`. Something has to
+//! keep one [`PendingRequests`] table keyed by mapping nonce, driving each
+//! entry's [`RetransmitTimer`] until a matching response arrives, the
+//! caller's [`RequestType`] budget is exhausted, or the configured max retry
+//! duration elapses, and resend the request datagram every time that timer
+//! fires. In this checkout that's [`crate::handle::Handle::request`] itself
+//! (there's no separate multiplexed client event loop here): it keeps a
+//! single-entry `PendingRequests` alive for the duration of one request and
+//! actually retransmits through it, rather than just waiting longer between
+//! checks of its id channel.
+
+use crate::handle::RequestType;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Initial retransmission time, as mandated by RFC 6887 §8.1.1.
+const IRT: Duration = Duration::from_secs(3);
+/// Maximum retransmission time, as mandated by RFC 6887 §8.1.1.
+pub(crate) const MRT: Duration = Duration::from_secs(1024);
+
+/// Draws `RAND`, uniform in `[-0.1, +0.1]` as required by RFC 6887 §8.1.1.
+pub fn jitter() -> f64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+    jitter_from_nanos(nanos)
+}
+
+/// Maps a count of nanoseconds within a second (`0..1_000_000_000`) onto
+/// `[-0.1, +0.1]`, split out from [`jitter`] so the formula itself can be
+/// tested against known inputs instead of only the live clock.
+fn jitter_from_nanos(nanos: u32) -> f64 {
+    (f64::from(nanos) / 1_000_000_000.0) * 0.2 - 0.1
+}
+
+/// Drives the `RT = (1 + RAND) * IRT`, `RT = (1 + RAND) * 2 * RT_prev`
+/// backoff schedule from RFC 6887 §8.1.1. Once the doubled interval would
+/// exceed `MRT`, every interval is `(1 + RAND) * MRT`.
+#[derive(Debug, Clone, Copy)]
+pub struct RetransmitTimer {
+    rt: Duration,
+    first: bool,
+}
+
+impl RetransmitTimer {
+    pub fn new() -> Self {
+        RetransmitTimer {
+            rt: IRT,
+            first: true,
+        }
+    }
+
+    /// Returns the next retransmission interval and advances the backoff.
+    pub fn next(&mut self, rand: f64) -> Duration {
+        debug_assert!((-0.1..=0.1).contains(&rand));
+        let rt = if self.first {
+            self.first = false;
+            IRT.mul_f64(1.0 + rand)
+        } else {
+            self.rt.mul_f64(2.0 * (1.0 + rand))
+        };
+        // Once the doubled interval would exceed MRT, retransmissions keep
+        // going out every MRT (still jittered by RAND) rather than settling
+        // on the exact same instant every time.
+        self.rt = if rt > MRT { MRT.mul_f64(1.0 + rand) } else { rt };
+        self.rt
+    }
+}
+
+impl Default for RetransmitTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A single in-flight request awaiting a response.
+struct PendingRequest<T> {
+    payload: T,
+    kind: RequestType,
+    attempts: usize,
+    timer: RetransmitTimer,
+    deadline: Instant,
+    max_duration: Option<Duration>,
+    started: Instant,
+}
+
+/// Whether a pending request should be retransmitted, given up on, or is
+/// still waiting for its next deadline.
+pub enum PendingOutcome<T> {
+    Retransmit(T),
+    Expired(T),
+    Waiting,
+}
+
+/// A table of in-flight requests keyed by mapping nonce, mirroring the
+/// `request_tasks`/pending-request maps kept by async RPC clients.
+pub struct PendingRequests<Id, T> {
+    entries: HashMap<Id, PendingRequest<T>>,
+}
+
+impl<Id: Eq + Hash, T> PendingRequests<Id, T> {
+    pub fn new() -> Self {
+        PendingRequests {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Registers a newly sent request, due for its first retransmission
+    /// after `RT = (1 + RAND) * IRT`.
+    pub fn insert(
+        &mut self,
+        id: Id,
+        payload: T,
+        kind: RequestType,
+        now: Instant,
+        rand: f64,
+        max_duration: Option<Duration>,
+    ) {
+        let mut timer = RetransmitTimer::new();
+        let deadline = now + timer.next(rand);
+        self.entries.insert(
+            id,
+            PendingRequest {
+                payload,
+                kind,
+                attempts: 1,
+                timer,
+                deadline,
+                max_duration,
+                started: now,
+            },
+        );
+    }
+
+    /// Removes and returns the payload of a request that got its response.
+    pub fn complete(&mut self, id: &Id) -> Option<T> {
+        self.entries.remove(id).map(|entry| entry.payload)
+    }
+
+    /// Returns the instant a still-tracked request is next due to be
+    /// checked, so a caller blocking on its response channel knows how long
+    /// it can afford to wait before calling [`PendingRequests::poll`].
+    pub fn deadline(&self, id: &Id) -> Option<Instant> {
+        self.entries.get(id).map(|entry| entry.deadline)
+    }
+
+    /// Checks a single pending request against `now`, advancing its timer
+    /// and retry count if it's due for retransmission, or removing it if
+    /// its `RequestType` budget or max duration has been exhausted.
+    pub fn poll(&mut self, id: &Id, now: Instant, rand: f64) -> PendingOutcome<T>
+    where
+        T: Clone,
+    {
+        let (expired, payload) = {
+            let entry = match self.entries.get_mut(id) {
+                Some(entry) => entry,
+                None => return PendingOutcome::Waiting,
+            };
+            if now < entry.deadline {
+                return PendingOutcome::Waiting;
+            }
+            let timed_out = entry
+                .max_duration
+                .is_some_and(|max| now.saturating_duration_since(entry.started) >= max);
+            // `RequestType` governs the mapping's *renewal* cadence, not how
+            // hard the initial request datagram gets retried: a dropped
+            // packet is equally likely regardless of kind, so only
+            // `Repeat(n)` (which bounds how many times the mapping itself
+            // gets re-requested) caps attempts here. `Once` and `KeepAlive`
+            // both keep retransmitting until `max_duration` (if any) elapses.
+            let budget_exhausted = matches!(entry.kind, RequestType::Repeat(n) if entry.attempts >= n);
+            if timed_out || budget_exhausted {
+                (true, entry.payload.clone())
+            } else {
+                entry.attempts += 1;
+                entry.deadline = now + entry.timer.next(rand);
+                (false, entry.payload.clone())
+            }
+        };
+        if expired {
+            self.entries.remove(id);
+            PendingOutcome::Expired(payload)
+        } else {
+            PendingOutcome::Retransmit(payload)
+        }
+    }
+}
+
+impl<Id: Eq + Hash, T> Default for PendingRequests<Id, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jitter_is_within_rfc_bounds() {
+        let rand = jitter();
+        assert!((-0.1..=0.1).contains(&rand));
+    }
+
+    #[test]
+    fn jitter_formula_spans_the_full_rfc_range() {
+        // Regression test for a divisor bug (`u32::MAX` instead of
+        // `1_000_000_000.0`) that kept every draw in roughly
+        // `[-0.1, -0.0534]`, so the positive half of the range was never hit.
+        assert!((jitter_from_nanos(0) - (-0.1)).abs() < 1e-12);
+        assert!((jitter_from_nanos(500_000_000) - 0.0).abs() < 1e-12);
+        assert!((jitter_from_nanos(1_000_000_000) - 0.1).abs() < 1e-12);
+    }
+
+    #[test]
+    fn timer_doubles_and_caps_at_mrt() {
+        let mut timer = RetransmitTimer::new();
+        assert_eq!(timer.next(0.0), IRT);
+        assert_eq!(timer.next(0.0), IRT * 2);
+        assert_eq!(timer.next(0.0), IRT * 4);
+
+        let mut last = IRT * 4;
+        while last < MRT {
+            last = timer.next(0.0);
+        }
+        assert_eq!(last, MRT);
+        // Stays capped (still jittered) on every subsequent call.
+        assert_eq!(timer.next(0.0), MRT);
+    }
+
+    #[test]
+    fn timer_applies_jitter() {
+        let mut timer = RetransmitTimer::new();
+        assert_eq!(timer.next(0.1), IRT.mul_f64(1.1));
+    }
+
+    #[test]
+    fn poll_waits_until_its_deadline() {
+        let mut table = PendingRequests::new();
+        let t0 = Instant::now();
+        table.insert("id", 42, RequestType::Repeat(3), t0, 0.0, None);
+        assert!(matches!(table.poll(&"id", t0, 0.0), PendingOutcome::Waiting));
+    }
+
+    #[test]
+    fn poll_retransmits_while_repeat_budget_remains() {
+        let mut table = PendingRequests::new();
+        let t0 = Instant::now();
+        table.insert("id", 42, RequestType::Repeat(3), t0, 0.0, None);
+        match table.poll(&"id", t0 + IRT, 0.0) {
+            PendingOutcome::Retransmit(payload) => assert_eq!(payload, 42),
+            _ => panic!("expected Retransmit"),
+        }
+    }
+
+    #[test]
+    fn poll_expires_once_repeat_budget_is_exhausted() {
+        let mut table = PendingRequests::new();
+        let t0 = Instant::now();
+        table.insert("id", 42, RequestType::Repeat(1), t0, 0.0, None);
+        match table.poll(&"id", t0 + IRT, 0.0) {
+            PendingOutcome::Expired(payload) => assert_eq!(payload, 42),
+            _ => panic!("expected Expired"),
+        }
+    }
+
+    #[test]
+    fn poll_does_not_exhaust_once_on_its_first_timeout() {
+        // A `Once` request still follows the backoff schedule instead of
+        // giving up after a single interval -- see the comment in `poll`.
+        let mut table = PendingRequests::new();
+        let t0 = Instant::now();
+        table.insert("id", 42, RequestType::Once, t0, 0.0, Some(Duration::from_secs(10)));
+        match table.poll(&"id", t0 + IRT, 0.0) {
+            PendingOutcome::Retransmit(payload) => assert_eq!(payload, 42),
+            _ => panic!("expected Retransmit, Once shouldn't exhaust on the first timeout"),
+        }
+    }
+
+    #[test]
+    fn deadline_tracks_the_entry_until_it_completes() {
+        let mut table = PendingRequests::new();
+        let t0 = Instant::now();
+        table.insert("id", 42, RequestType::Repeat(3), t0, 0.0, None);
+        assert_eq!(table.deadline(&"id"), Some(t0 + IRT));
+        table.complete(&"id");
+        assert_eq!(table.deadline(&"id"), None);
+    }
+
+    #[test]
+    fn poll_expires_once_when_max_duration_elapses() {
+        let mut table = PendingRequests::new();
+        let t0 = Instant::now();
+        table.insert("id", 42, RequestType::Once, t0, 0.0, Some(Duration::from_secs(1)));
+        match table.poll(&"id", t0 + IRT, 0.0) {
+            PendingOutcome::Expired(payload) => assert_eq!(payload, 42),
+            _ => panic!("expected Expired once max_duration elapses"),
+        }
+    }
+}